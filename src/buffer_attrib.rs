@@ -0,0 +1,34 @@
+use wasm_bindgen::prelude::*;
+use web_sys::{WebGl2RenderingContext, WebGlBuffer};
+
+/// Describes how a single `WebGlBuffer` should be bound to a vertex attribute.
+pub struct BufferAttrib<'a> {
+  pub name: String,
+  pub buffer: &'a WebGlBuffer,
+  pub target: u32,
+  pub num_components: i32,
+  pub buffer_type: u32,
+  pub normalize: bool,
+  pub stride: i32,
+  pub offset: i32,
+}
+
+/// Bind `attrib.buffer` to `location` and enable the attribute.
+pub fn bind_buffer_to_attrib(
+  gl_context: &WebGl2RenderingContext,
+  attrib: &BufferAttrib,
+  location: u32,
+) -> Result<(), JsValue> {
+  gl_context.bind_buffer(attrib.target, Some(attrib.buffer));
+  gl_context.vertex_attrib_pointer_with_i32(
+    location,
+    attrib.num_components,
+    attrib.buffer_type,
+    attrib.normalize,
+    attrib.stride,
+    attrib.offset,
+  );
+  gl_context.enable_vertex_attrib_array(location);
+
+  Ok(())
+}
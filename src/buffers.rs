@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+use web_sys::{WebGl2RenderingContext, WebGlBuffer};
+
+use crate::obj::Mesh;
+
+/// Build the `WebGlBuffer`s backing `mesh`, including the `ELEMENT_ARRAY_BUFFER` of indices
+/// `draw_scene` feeds to `drawElements`. Vertex color isn't part of the OBJ format, so every
+/// vertex gets a flat white tint that the texture/lighting passes then modulate.
+pub fn make_buffers(
+  gl_context: &WebGl2RenderingContext,
+  mesh: &Mesh,
+) -> Result<HashMap<String, WebGlBuffer>, JsValue> {
+  let mut buffers = HashMap::new();
+
+  buffers.insert("vertices".to_string(), make_f32_buffer(gl_context, &mesh.positions)?);
+  buffers.insert("normals".to_string(), make_f32_buffer(gl_context, &mesh.normals)?);
+  buffers.insert("tex_coords".to_string(), make_f32_buffer(gl_context, &mesh.tex_coords)?);
+
+  let colors = vec![1.0f32; mesh.positions.len() / 3 * 4];
+  buffers.insert("colors".to_string(), make_f32_buffer(gl_context, &colors)?);
+
+  buffers.insert("indices".to_string(), make_u16_element_buffer(gl_context, &mesh.indices)?);
+
+  Ok(buffers)
+}
+
+fn make_f32_buffer(
+  gl_context: &WebGl2RenderingContext,
+  data: &[f32],
+) -> Result<WebGlBuffer, JsValue> {
+  let buffer = gl_context.create_buffer().ok_or("Failed to create buffer")?;
+  gl_context.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&buffer));
+
+  // Safe as long as no allocation happens on the Wasm heap before the view is dropped.
+  let array = unsafe { js_sys::Float32Array::view(data) };
+  gl_context.buffer_data_with_array_buffer_view(
+    WebGl2RenderingContext::ARRAY_BUFFER,
+    &array,
+    WebGl2RenderingContext::STATIC_DRAW,
+  );
+
+  Ok(buffer)
+}
+
+fn make_u16_element_buffer(
+  gl_context: &WebGl2RenderingContext,
+  data: &[u16],
+) -> Result<WebGlBuffer, JsValue> {
+  let buffer = gl_context.create_buffer().ok_or("Failed to create buffer")?;
+  gl_context.bind_buffer(WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER, Some(&buffer));
+
+  // Safe as long as no allocation happens on the Wasm heap before the view is dropped.
+  let array = unsafe { js_sys::Uint16Array::view(data) };
+  gl_context.buffer_data_with_array_buffer_view(
+    WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER,
+    &array,
+    WebGl2RenderingContext::STATIC_DRAW,
+  );
+
+  Ok(buffer)
+}
@@ -0,0 +1,47 @@
+use nalgebra_glm::{look_at, vec3, Mat4, Vec3};
+
+const MIN_ELEVATION: f32 = -1.5;
+const MAX_ELEVATION: f32 = 1.5;
+const MIN_RADIUS: f32 = 2.0;
+const MAX_RADIUS: f32 = 50.0;
+
+/// A camera that orbits `target` at `radius` units, positioned by `azimuth`/`elevation` rather
+/// than a raw `eye` so drag/scroll input can update it without re-deriving spherical angles.
+#[derive(Clone)]
+pub struct Camera {
+  pub target: Vec3,
+  pub up: Vec3,
+  pub azimuth: f32,
+  pub elevation: f32,
+  pub radius: f32,
+}
+
+impl Camera {
+  pub fn new() -> Self {
+    Camera { target: vec3(0.0, 0.0, 0.0), up: vec3(0.0, 1.0, 0.0), azimuth: 0.0, elevation: 0.0, radius: 6.0 }
+  }
+
+  pub fn eye(&self) -> Vec3 {
+    let horizontal_radius = self.radius * self.elevation.cos();
+    let offset = vec3(
+      horizontal_radius * self.azimuth.sin(),
+      self.radius * self.elevation.sin(),
+      horizontal_radius * self.azimuth.cos(),
+    );
+    self.target + offset
+  }
+
+  pub fn view_matrix(&self) -> Mat4 {
+    look_at(&self.eye(), &self.target, &self.up)
+  }
+
+  /// Orbit around `target`; `delta_azimuth`/`delta_elevation` are in radians.
+  pub fn orbit(&mut self, delta_azimuth: f32, delta_elevation: f32) {
+    self.azimuth += delta_azimuth;
+    self.elevation = (self.elevation + delta_elevation).clamp(MIN_ELEVATION, MAX_ELEVATION);
+  }
+
+  pub fn zoom(&mut self, delta_radius: f32) {
+    self.radius = (self.radius + delta_radius).clamp(MIN_RADIUS, MAX_RADIUS);
+  }
+}
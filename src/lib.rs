@@ -0,0 +1,27 @@
+mod buffer_attrib;
+mod buffers;
+mod camera;
+mod obj;
+mod program_info;
+mod shaders;
+mod texture;
+mod utils;
+
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{HtmlCanvasElement, WebGl2RenderingContext};
+
+#[wasm_bindgen(start)]
+pub fn main() -> Result<(), JsValue> {
+  let window = web_sys::window().ok_or("Failed to get window")?;
+  let document = window.document().ok_or("Failed to get document")?;
+  let canvas =
+    document.get_element_by_id("canvas").ok_or("Failed to find element with id `canvas`")?;
+  let canvas: HtmlCanvasElement = canvas.dyn_into::<HtmlCanvasElement>()?;
+
+  let gl_context = canvas
+    .get_context("webgl2")?
+    .ok_or("Failed to get `webgl2` context")?
+    .dyn_into::<WebGl2RenderingContext>()?;
+
+  shaders::do_webgl(gl_context)
+}
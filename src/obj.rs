@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+/// Flat, de-indexed vertex data ready to upload straight into `WebGlBuffer`s, plus a `u16`
+/// index array for `drawElements`.
+pub struct Mesh {
+  pub positions: Vec<f32>,
+  pub normals: Vec<f32>,
+  pub tex_coords: Vec<f32>,
+  pub indices: Vec<u16>,
+}
+
+/// Parse Wavefront OBJ `source`, re-indexing its `v`/`vn`/`vt` lines so that every distinct
+/// `v/vt/vn` triple referenced by an `f` line becomes exactly one vertex in the output.
+pub fn parse_obj(source: &str) -> Result<Mesh, String> {
+  let mut raw_positions = Vec::new();
+  let mut raw_normals = Vec::new();
+  let mut raw_tex_coords = Vec::new();
+
+  let mut positions = Vec::new();
+  let mut normals = Vec::new();
+  let mut tex_coords = Vec::new();
+  let mut indices = Vec::new();
+  let mut vertex_cache: HashMap<(i32, i32, i32), u16> = HashMap::new();
+
+  for line in source.lines() {
+    let mut tokens = line.split_whitespace();
+    match tokens.next() {
+      Some("v") => raw_positions.push(parse_floats::<3>(tokens, "v")?),
+      Some("vn") => raw_normals.push(parse_floats::<3>(tokens, "vn")?),
+      Some("vt") => raw_tex_coords.push(parse_floats::<2>(tokens, "vt")?),
+      Some("f") => {
+        for token in tokens {
+          let key = parse_face_vertex(token)?;
+          let index = match vertex_cache.get(&key) {
+            Some(&index) => index,
+            None => {
+              let (v, vt, vn) = key;
+              // Push a full entry (zero-filled where the face omitted `vt`/`vn`) so
+              // positions/normals/tex_coords stay the same length and share the index below.
+              let position = raw_positions
+                .get((v - 1) as usize)
+                .ok_or_else(|| format!("Face references out-of-range vertex index: {}", v))?;
+              positions.extend_from_slice(position);
+
+              let normal = if vn > 0 {
+                *raw_normals
+                  .get((vn - 1) as usize)
+                  .ok_or_else(|| format!("Face references out-of-range normal index: {}", vn))?
+              } else {
+                [0.0, 0.0, 0.0]
+              };
+              normals.extend_from_slice(&normal);
+
+              let tex_coord = if vt > 0 {
+                *raw_tex_coords
+                  .get((vt - 1) as usize)
+                  .ok_or_else(|| format!("Face references out-of-range texcoord index: {}", vt))?
+              } else {
+                [0.0, 0.0]
+              };
+              tex_coords.extend_from_slice(&tex_coord);
+
+              let index = (positions.len() / 3 - 1) as u16;
+              vertex_cache.insert(key, index);
+              index
+            }
+          };
+          indices.push(index);
+        }
+      }
+      _ => {}
+    }
+  }
+
+  Ok(Mesh { positions, normals, tex_coords, indices })
+}
+
+fn parse_floats<'a, const N: usize>(
+  tokens: impl Iterator<Item = &'a str>,
+  directive: &str,
+) -> Result<[f32; N], String> {
+  let values: Vec<f32> = tokens
+    .map(|token| token.parse::<f32>().map_err(|_| format!("Invalid `{}` value: {}", directive, token)))
+    .collect::<Result<_, _>>()?;
+  values
+    .try_into()
+    .map_err(|values: Vec<f32>| format!("Expected {} values after `{}`, got {}", N, directive, values.len()))
+}
+
+/// Parse a single `f` line token such as `3`, `3/1`, `3//2`, or `3/1/2` into 1-based
+/// `(position, tex_coord, normal)` indices, using `0` for any component that is absent.
+fn parse_face_vertex(token: &str) -> Result<(i32, i32, i32), String> {
+  let mut components = token.split('/');
+  let v = components
+    .next()
+    .ok_or_else(|| format!("Empty face vertex: {}", token))?
+    .parse::<i32>()
+    .map_err(|_| format!("Invalid face vertex index: {}", token))?;
+  let vt = match components.next() {
+    Some("") | None => 0,
+    Some(value) => value.parse::<i32>().map_err(|_| format!("Invalid face vertex index: {}", token))?,
+  };
+  let vn = match components.next() {
+    Some("") | None => 0,
+    Some(value) => value.parse::<i32>().map_err(|_| format!("Invalid face vertex index: {}", token))?,
+  };
+
+  Ok((v, vt, vn))
+}
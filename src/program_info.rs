@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+use web_sys::{WebGl2RenderingContext, WebGlProgram, WebGlShader, WebGlUniformLocation};
+
+const VERTEX_SHADER_SOURCE: &str = r#"#version 300 es
+in vec3 a_vertex_position;
+in vec4 a_vertex_color;
+in vec2 a_texture_coord;
+in vec3 a_vertex_normal;
+
+uniform mat4 u_model_view_matrix;
+uniform mat4 u_projection_matrix;
+uniform mat4 u_normal_matrix;
+
+out vec4 v_color;
+out vec2 v_texcoord;
+out vec3 v_normal;
+
+void main() {
+  gl_Position = u_projection_matrix * u_model_view_matrix * vec4(a_vertex_position, 1.0);
+  v_color = a_vertex_color;
+  v_texcoord = a_texture_coord;
+  v_normal = mat3(u_normal_matrix) * a_vertex_normal;
+}
+"#;
+
+const FRAGMENT_SHADER_SOURCE: &str = r#"#version 300 es
+precision highp float;
+
+in vec4 v_color;
+in vec2 v_texcoord;
+in vec3 v_normal;
+uniform float u_time;
+uniform sampler2D u_sampler;
+uniform vec3 u_light_direction;
+
+out vec4 out_color;
+
+void main() {
+  vec3 ambient_light = vec3(0.3, 0.3, 0.3);
+  float diffuse = max(dot(normalize(v_normal), normalize(u_light_direction)), 0.0);
+  vec3 lighting = ambient_light + vec3(diffuse);
+
+  vec4 base_color = texture(u_sampler, v_texcoord) * v_color;
+  out_color = vec4(base_color.rgb * lighting, base_color.a);
+}
+"#;
+
+/// Holds the compiled shader program along with the locations of the
+/// attributes and uniforms `draw_scene` needs to bind every frame.
+#[derive(Clone)]
+pub struct ProgramInfo {
+  pub program: WebGlProgram,
+  pub attrib_locations: HashMap<String, i32>,
+  pub uniform_locations: HashMap<String, Option<WebGlUniformLocation>>,
+}
+
+impl ProgramInfo {
+  pub fn new(gl_context: &WebGl2RenderingContext) -> Result<Self, JsValue> {
+    let vertex_shader = compile_shader(
+      gl_context,
+      WebGl2RenderingContext::VERTEX_SHADER,
+      VERTEX_SHADER_SOURCE,
+    )?;
+    let fragment_shader = compile_shader(
+      gl_context,
+      WebGl2RenderingContext::FRAGMENT_SHADER,
+      FRAGMENT_SHADER_SOURCE,
+    )?;
+    let program = link_program(gl_context, &vertex_shader, &fragment_shader)?;
+
+    let mut attrib_locations = HashMap::new();
+    attrib_locations.insert(
+      "a_vertex_position".to_string(),
+      gl_context.get_attrib_location(&program, "a_vertex_position"),
+    );
+    attrib_locations.insert(
+      "a_vertex_color".to_string(),
+      gl_context.get_attrib_location(&program, "a_vertex_color"),
+    );
+    attrib_locations.insert(
+      "a_texture_coord".to_string(),
+      gl_context.get_attrib_location(&program, "a_texture_coord"),
+    );
+    attrib_locations.insert(
+      "a_vertex_normal".to_string(),
+      gl_context.get_attrib_location(&program, "a_vertex_normal"),
+    );
+
+    let mut uniform_locations = HashMap::new();
+    uniform_locations.insert(
+      "u_projection_matrix".to_string(),
+      gl_context.get_uniform_location(&program, "u_projection_matrix"),
+    );
+    uniform_locations.insert(
+      "u_model_view_matrix".to_string(),
+      gl_context.get_uniform_location(&program, "u_model_view_matrix"),
+    );
+    uniform_locations
+      .insert("u_time".to_string(), gl_context.get_uniform_location(&program, "u_time"));
+    uniform_locations
+      .insert("u_sampler".to_string(), gl_context.get_uniform_location(&program, "u_sampler"));
+    uniform_locations.insert(
+      "u_normal_matrix".to_string(),
+      gl_context.get_uniform_location(&program, "u_normal_matrix"),
+    );
+    uniform_locations.insert(
+      "u_light_direction".to_string(),
+      gl_context.get_uniform_location(&program, "u_light_direction"),
+    );
+
+    Ok(ProgramInfo { program, attrib_locations, uniform_locations })
+  }
+}
+
+fn compile_shader(
+  gl_context: &WebGl2RenderingContext,
+  shader_type: u32,
+  source: &str,
+) -> Result<WebGlShader, JsValue> {
+  let shader =
+    gl_context.create_shader(shader_type).ok_or("Failed to create shader")?;
+  gl_context.shader_source(&shader, source);
+  gl_context.compile_shader(&shader);
+
+  if gl_context
+    .get_shader_parameter(&shader, WebGl2RenderingContext::COMPILE_STATUS)
+    .as_bool()
+    .unwrap_or(false)
+  {
+    Ok(shader)
+  } else {
+    Err(
+      gl_context
+        .get_shader_info_log(&shader)
+        .unwrap_or_else(|| "Unknown error compiling shader".to_string())
+        .into(),
+    )
+  }
+}
+
+fn link_program(
+  gl_context: &WebGl2RenderingContext,
+  vertex_shader: &WebGlShader,
+  fragment_shader: &WebGlShader,
+) -> Result<WebGlProgram, JsValue> {
+  let program = gl_context.create_program().ok_or("Failed to create program")?;
+  gl_context.attach_shader(&program, vertex_shader);
+  gl_context.attach_shader(&program, fragment_shader);
+  gl_context.link_program(&program);
+
+  if gl_context
+    .get_program_parameter(&program, WebGl2RenderingContext::LINK_STATUS)
+    .as_bool()
+    .unwrap_or(false)
+  {
+    Ok(program)
+  } else {
+    Err(
+      gl_context
+        .get_program_info_log(&program)
+        .unwrap_or_else(|| "Unknown error linking program".to_string())
+        .into(),
+    )
+  }
+}
@@ -1,19 +1,28 @@
 use super::*;
 use crate::{
-  buffer_attrib, buffer_attrib::BufferAttrib, buffers, program_info::ProgramInfo, utils::*,
+  buffer_attrib, buffer_attrib::BufferAttrib, buffers, camera::Camera, obj,
+  program_info::ProgramInfo, texture, utils::*,
 };
 use nalgebra_glm;
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 use wasm_bindgen::{prelude::*, JsCast};
 use web_sys::{
-  console, AudioContext, EventTarget, HtmlCanvasElement, HtmlMediaElement, WebGl2RenderingContext,
-  WebGlBuffer,
+  console, EventTarget, HtmlCanvasElement, HtmlImageElement, PointerEvent, WebGl2RenderingContext,
+  WebGlBuffer, WebGlTexture, WheelEvent,
 };
 
+/// Radians of orbit per pixel of pointer drag.
+const ORBIT_SENSITIVITY: f32 = 0.01;
+/// Units of camera radius per "line" of wheel scroll.
+const ZOOM_SENSITIVITY: f32 = 0.01;
+
 pub fn draw_scene(
   gl_context: &WebGl2RenderingContext,
   program_info: ProgramInfo,
   buffers: HashMap<String, WebGlBuffer>,
+  texture: WebGlTexture,
+  index_count: i32,
+  view_matrix: nalgebra_glm::Mat4,
   time: f32,
 ) -> Result<(), JsValue> {
   gl_context.clear_color(1.0, 0.5, 0.5, 1.0);
@@ -25,9 +34,17 @@ pub fn draw_scene(
   gl_context
     .clear(WebGl2RenderingContext::COLOR_BUFFER_BIT | WebGl2RenderingContext::DEPTH_BUFFER_BIT);
 
-  // Projection and model view matrices
-  let projection_matrix = create_perspective_matrix(&gl_context)?;
-  let model_view_matrix = create_model_view_matrix(time);
+  // Keep the drawing buffer in sync with the canvas's display size (and the device's pixel
+  // ratio) before computing the projection matrix, so the aspect ratio always matches what's
+  // actually on screen.
+  let (width, height) = resize_canvas_to_display_size(&gl_context)?;
+
+  // Projection, model view and normal matrices
+  let projection_matrix = create_perspective_matrix(width, height);
+  let model_matrix = create_model_matrix(time);
+  let model_view_matrix = view_matrix * model_matrix;
+  let normal_matrix = create_normal_matrix(&model_view_matrix);
+  let model_view_matrix = mat4_to_f32_16(model_view_matrix);
 
   // Tell WebGl to pull out the positions from the vertices buffer into the `a_vertex_position` attribute
   let a_vertex_position = (*program_info
@@ -41,7 +58,7 @@ pub fn draw_scene(
       .get(&"vertices".to_string())
       .ok_or("Failed to get `a_vertex_position` attribute")?,
     target: WebGl2RenderingContext::ARRAY_BUFFER,
-    num_components: 2,
+    num_components: 3,
     buffer_type: WebGl2RenderingContext::FLOAT,
     normalize: false,
     stride: 0,
@@ -69,6 +86,44 @@ pub fn draw_scene(
   };
   buffer_attrib::bind_buffer_to_attrib(&gl_context, &a_vertex_color_buffer_attrib, a_vertex_color)?;
 
+  let a_texture_coord = (*program_info
+    .attrib_locations
+    .get(&"a_texture_coord".to_string())
+    .ok_or("Failed to get `a_texture_coord` attribute")?) as u32;
+  let a_texture_coord_buffer_attrib = BufferAttrib {
+    name: "tex_coords".into(),
+    buffer: buffers
+      .get(&"tex_coords".to_string())
+      .ok_or("Failed to get `a_texture_coord` attribute")?,
+    target: WebGl2RenderingContext::ARRAY_BUFFER,
+    num_components: 2,
+    buffer_type: WebGl2RenderingContext::FLOAT,
+    normalize: false,
+    stride: 0,
+    offset: 0,
+  };
+  buffer_attrib::bind_buffer_to_attrib(
+    &gl_context,
+    &a_texture_coord_buffer_attrib,
+    a_texture_coord,
+  )?;
+
+  let a_vertex_normal = (*program_info
+    .attrib_locations
+    .get(&"a_vertex_normal".to_string())
+    .ok_or("Failed to get `a_vertex_normal` attribute")?) as u32;
+  let a_vertex_normal_buffer_attrib = BufferAttrib {
+    name: "normals".into(),
+    buffer: buffers.get(&"normals".to_string()).ok_or("Failed to get `a_vertex_normal` attribute")?,
+    target: WebGl2RenderingContext::ARRAY_BUFFER,
+    num_components: 3,
+    buffer_type: WebGl2RenderingContext::FLOAT,
+    normalize: false,
+    stride: 0,
+    offset: 0,
+  };
+  buffer_attrib::bind_buffer_to_attrib(&gl_context, &a_vertex_normal_buffer_attrib, a_vertex_normal)?;
+
   // Tell WebGl to use our program when drawing
   gl_context.use_program(Some(&program_info.program));
 
@@ -89,37 +144,89 @@ pub fn draw_scene(
   gl_context
     .uniform1f(program_info.uniform_locations.get(&"u_time".to_string()).unwrap().as_ref(), time);
 
-  let vertex_count = 4;
-  let offset = 0; // How many bytes inside the buffer to start from
-  gl_context.draw_arrays(WebGl2RenderingContext::TRIANGLE_STRIP, offset, vertex_count);
+  let normal_matrix = &normal_matrix[0..];
+  gl_context.uniform_matrix4fv_with_f32_array(
+    program_info.uniform_locations.get(&"u_normal_matrix".to_string()).unwrap().as_ref(),
+    false,
+    normal_matrix,
+  );
+
+  gl_context.uniform3f(
+    program_info.uniform_locations.get(&"u_light_direction".to_string()).unwrap().as_ref(),
+    0.85,
+    0.8,
+    0.75,
+  );
+
+  gl_context.active_texture(WebGl2RenderingContext::TEXTURE0);
+  gl_context.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&texture));
+  gl_context.uniform1i(
+    program_info.uniform_locations.get(&"u_sampler".to_string()).unwrap().as_ref(),
+    0,
+  );
+
+  gl_context.bind_buffer(
+    WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER,
+    buffers.get(&"indices".to_string()),
+  );
+
+  let offset = 0; // How many bytes inside the index buffer to start from
+  gl_context.draw_elements_with_i32(
+    WebGl2RenderingContext::TRIANGLES,
+    index_count,
+    WebGl2RenderingContext::UNSIGNED_SHORT,
+    offset,
+  );
 
   Ok(())
 }
 
-fn create_perspective_matrix(gl_context: &WebGl2RenderingContext) -> Result<[f32; 16], JsValue> {
+fn create_perspective_matrix(width: i32, height: i32) -> [f32; 16] {
   // Create a perspective matrix, a special matrix that is used to simulate the distortion of perspective in a camera.
   // Our field of view is 45 degrees, which a width/height ratio that matches the display size of the canvas and we
   // only want to see objects between 0.1 and 100.0 units away from the camera
   let field_of_view = 45.0 * std::f32::consts::PI / 180.0;
-  let canvas: HtmlCanvasElement = gl_context
-    .canvas()
-    .ok_or("Failed to get canvas on draw")?
-    .dyn_into::<web_sys::HtmlCanvasElement>()?;
-  let aspect = (canvas.client_width() / canvas.client_height()) as f32;
+  let aspect = width as f32 / height as f32;
   let z_near = 0.1;
   let z_far = 100.0;
   let projection_matrix = nalgebra_glm::perspective(aspect, field_of_view, z_near, z_far);
-  Ok(mat4_to_f32_16(projection_matrix))
+  mat4_to_f32_16(projection_matrix)
 }
 
-/// Rotate the square
-fn create_model_view_matrix(angle: f32) -> [f32; 16] {
-  let model_view_matrix = nalgebra_glm::identity();
-  let translation_vector = nalgebra_glm::vec3(0.0, 0.0, -6.0);
-  let translated_matrix = nalgebra_glm::translate(&model_view_matrix, &translation_vector);
+/// Resize the canvas's drawing buffer (and GL viewport) to match its CSS display size scaled
+/// by the device pixel ratio, so rendering stays crisp and undistorted on HiDPI screens and
+/// after the canvas is resized. Returns the drawing buffer's `(width, height)` in pixels.
+fn resize_canvas_to_display_size(gl_context: &WebGl2RenderingContext) -> Result<(i32, i32), JsValue> {
+  let canvas: HtmlCanvasElement = gl_context
+    .canvas()
+    .ok_or("Failed to get canvas on resize")?
+    .dyn_into::<web_sys::HtmlCanvasElement>()?;
+  let device_pixel_ratio = web_sys::window().ok_or("Failed to get window")?.device_pixel_ratio();
+
+  let width = (canvas.client_width() as f64 * device_pixel_ratio) as i32;
+  let height = (canvas.client_height() as f64 * device_pixel_ratio) as i32;
+
+  if canvas.width() != width as u32 || canvas.height() != height as u32 {
+    canvas.set_width(width as u32);
+    canvas.set_height(height as u32);
+    gl_context.viewport(0, 0, width, height);
+  }
+
+  Ok((width, height))
+}
+
+/// Spin the square about its own z axis; camera positioning is `Camera`'s job now.
+fn create_model_matrix(angle: f32) -> nalgebra_glm::Mat4 {
+  let model_matrix = nalgebra_glm::identity();
   let rotation_vector = nalgebra_glm::vec3(0.0, 0.0, 1.0);
-  let rotated_matrix = nalgebra_glm::rotate(&translated_matrix, angle, &rotation_vector);
-  mat4_to_f32_16(rotated_matrix)
+  nalgebra_glm::rotate(&model_matrix, angle, &rotation_vector)
+}
+
+/// The inverse-transpose of the model-view matrix, so normals stay perpendicular to the
+/// surface under non-uniform scaling instead of just being rotated/translated like positions.
+fn create_normal_matrix(model_view_matrix: &nalgebra_glm::Mat4) -> [f32; 16] {
+  let inverted = model_view_matrix.try_inverse().unwrap_or_else(nalgebra_glm::identity);
+  mat4_to_f32_16(inverted.transpose())
 }
 
 pub(crate) fn do_webgl(gl_context: WebGl2RenderingContext) -> Result<(), JsValue> {
@@ -127,14 +234,37 @@ pub(crate) fn do_webgl(gl_context: WebGl2RenderingContext) -> Result<(), JsValue
 
   let program_info = ProgramInfo::new(&gl_context)?;
 
-  let buffers = buffers::make_buffers(&gl_context)?;
+  let mesh = obj::parse_obj(include_str!("../assets/square.obj")).map_err(JsValue::from)?;
+  let index_count = mesh.indices.len() as i32;
+  let buffers = buffers::make_buffers(&gl_context, &mesh)?;
+
+  let texture = Rc::new(RefCell::new(texture::create_placeholder_texture(
+    &gl_context,
+    [0, 0, 255, 255],
+  )?));
+  load_texture_image(&gl_context, &texture, "assets/crate.png")?;
+
+  let canvas: HtmlCanvasElement =
+    gl_context.canvas().ok_or("Failed to get canvas")?.dyn_into::<HtmlCanvasElement>()?;
+  let camera = Rc::new(RefCell::new(Camera::new()));
+  bind_orbit_controls(&canvas, &camera)?;
 
   // Draw scene every 0.01 seconds
   let ref_count = Rc::new(RefCell::new(None));
   let ref_count_clone = ref_count.clone();
 
   *ref_count_clone.borrow_mut() = Some(Closure::wrap(Box::new(move |t| {
-    draw_scene(&gl_context.clone(), program_info.clone(), buffers.clone(), t * 0.001f32).unwrap();
+    let view_matrix = camera.borrow().view_matrix();
+    draw_scene(
+      &gl_context.clone(),
+      program_info.clone(),
+      buffers.clone(),
+      texture.borrow().clone(),
+      index_count,
+      view_matrix,
+      t * 0.001f32,
+    )
+    .unwrap();
     request_animation_frame(ref_count.borrow().as_ref().unwrap());
   }) as Box<dyn FnMut(f32)>));
 
@@ -142,3 +272,91 @@ pub(crate) fn do_webgl(gl_context: WebGl2RenderingContext) -> Result<(), JsValue
 
   Ok(())
 }
+
+/// Asynchronously load `url` into a fresh `HtmlImageElement` and, once it loads, swap the
+/// decoded texture into `texture` in place. Logs via `console::error_1` on failure instead of
+/// leaving the placeholder stuck with no indication anything went wrong.
+fn load_texture_image(
+  gl_context: &WebGl2RenderingContext,
+  texture: &Rc<RefCell<WebGlTexture>>,
+  url: &str,
+) -> Result<(), JsValue> {
+  let image = HtmlImageElement::new()?;
+
+  let gl_context_clone = gl_context.clone();
+  let texture_clone = texture.clone();
+  let image_clone = image.clone();
+  let on_load = Closure::wrap(Box::new(move || {
+    match texture::create_texture_from_image(&gl_context_clone, &image_clone) {
+      Ok(loaded_texture) => *texture_clone.borrow_mut() = loaded_texture,
+      Err(err) => console::error_1(&err),
+    }
+  }) as Box<dyn FnMut()>);
+  image.set_onload(Some(on_load.as_ref().unchecked_ref()));
+  on_load.forget();
+
+  let image_clone = image.clone();
+  let on_error = Closure::wrap(Box::new(move |_event: JsValue| {
+    console::error_1(&format!("Failed to load texture image: {}", image_clone.src()).into());
+  }) as Box<dyn FnMut(JsValue)>);
+  image.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+  on_error.forget();
+
+  image.set_cross_origin(Some("anonymous"));
+  image.set_src(url);
+
+  Ok(())
+}
+
+/// Wire up pointer-drag orbiting and wheel-zoom on `canvas`, mutating `camera` in place.
+fn bind_orbit_controls(
+  canvas: &HtmlCanvasElement,
+  camera: &Rc<RefCell<Camera>>,
+) -> Result<(), JsValue> {
+  let event_target: &EventTarget = canvas.as_ref();
+  let dragging_from = Rc::new(RefCell::new(None::<(f32, f32)>));
+
+  let dragging_from_clone = dragging_from.clone();
+  let canvas_clone = canvas.clone();
+  let on_pointer_down = Closure::wrap(Box::new(move |event: PointerEvent| {
+    // Capture the pointer so drags that leave the canvas (or the button release) still reach
+    // us, instead of leaving `dragging_from` stuck `Some` and the camera spinning forever.
+    let _ = canvas_clone.set_pointer_capture(event.pointer_id());
+    *dragging_from_clone.borrow_mut() = Some((event.client_x() as f32, event.client_y() as f32));
+  }) as Box<dyn FnMut(PointerEvent)>);
+  event_target.add_event_listener_with_callback("pointerdown", on_pointer_down.as_ref().unchecked_ref())?;
+  on_pointer_down.forget();
+
+  let dragging_from_clone = dragging_from.clone();
+  let camera_clone = camera.clone();
+  let on_pointer_move = Closure::wrap(Box::new(move |event: PointerEvent| {
+    let mut dragging_from = dragging_from_clone.borrow_mut();
+    if let Some((last_x, last_y)) = *dragging_from {
+      let (x, y) = (event.client_x() as f32, event.client_y() as f32);
+      camera_clone
+        .borrow_mut()
+        .orbit(-(x - last_x) * ORBIT_SENSITIVITY, -(y - last_y) * ORBIT_SENSITIVITY);
+      *dragging_from = Some((x, y));
+    }
+  }) as Box<dyn FnMut(PointerEvent)>);
+  event_target.add_event_listener_with_callback("pointermove", on_pointer_move.as_ref().unchecked_ref())?;
+  on_pointer_move.forget();
+
+  let dragging_from_clone = dragging_from.clone();
+  let on_pointer_end = Closure::wrap(Box::new(move |_event: PointerEvent| {
+    *dragging_from_clone.borrow_mut() = None;
+  }) as Box<dyn FnMut(PointerEvent)>);
+  event_target.add_event_listener_with_callback("pointerup", on_pointer_end.as_ref().unchecked_ref())?;
+  event_target.add_event_listener_with_callback("pointercancel", on_pointer_end.as_ref().unchecked_ref())?;
+  on_pointer_end.forget();
+
+  let camera_clone = camera.clone();
+  let on_wheel = Closure::wrap(Box::new(move |event: WheelEvent| {
+    event.prevent_default();
+    camera_clone.borrow_mut().zoom(event.delta_y() as f32 * ZOOM_SENSITIVITY);
+  }) as Box<dyn FnMut(WheelEvent)>);
+  event_target.add_event_listener_with_callback("wheel", on_wheel.as_ref().unchecked_ref())?;
+  on_wheel.forget();
+
+  Ok(())
+}
@@ -0,0 +1,67 @@
+use wasm_bindgen::prelude::*;
+use web_sys::{HtmlImageElement, WebGl2RenderingContext, WebGlTexture};
+
+/// Create a `WebGlTexture` filled with a single pixel, suitable for binding and drawing before
+/// a real image has finished loading.
+pub fn create_placeholder_texture(
+  gl_context: &WebGl2RenderingContext,
+  pixel: [u8; 4],
+) -> Result<WebGlTexture, JsValue> {
+  let texture = gl_context.create_texture().ok_or("Failed to create texture")?;
+  gl_context.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&texture));
+  gl_context.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+    WebGl2RenderingContext::TEXTURE_2D,
+    0,
+    WebGl2RenderingContext::RGBA as i32,
+    1,
+    1,
+    0,
+    WebGl2RenderingContext::RGBA,
+    WebGl2RenderingContext::UNSIGNED_BYTE,
+    Some(&pixel),
+  )?;
+
+  Ok(texture)
+}
+
+/// Create a `WebGlTexture` from an already-loaded `HtmlImageElement`, setting `TEXTURE_2D`
+/// filtering/wrap params and generating mipmaps when the image's dimensions are powers of two.
+pub fn create_texture_from_image(
+  gl_context: &WebGl2RenderingContext,
+  image: &HtmlImageElement,
+) -> Result<WebGlTexture, JsValue> {
+  let texture = gl_context.create_texture().ok_or("Failed to create texture")?;
+  gl_context.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&texture));
+  gl_context.tex_image_2d_with_u32_and_u32_and_html_image_element(
+    WebGl2RenderingContext::TEXTURE_2D,
+    0,
+    WebGl2RenderingContext::RGBA as i32,
+    WebGl2RenderingContext::RGBA,
+    WebGl2RenderingContext::UNSIGNED_BYTE,
+    image,
+  )?;
+
+  if image.width().is_power_of_two() && image.height().is_power_of_two() {
+    gl_context.generate_mipmap(WebGl2RenderingContext::TEXTURE_2D);
+  } else {
+    // WebGL2 can't mipmap non-power-of-two textures; clamp instead of repeat and drop to linear
+    // filtering so sampling stays well-defined at the edges.
+    gl_context.tex_parameteri(
+      WebGl2RenderingContext::TEXTURE_2D,
+      WebGl2RenderingContext::TEXTURE_WRAP_S,
+      WebGl2RenderingContext::CLAMP_TO_EDGE as i32,
+    );
+    gl_context.tex_parameteri(
+      WebGl2RenderingContext::TEXTURE_2D,
+      WebGl2RenderingContext::TEXTURE_WRAP_T,
+      WebGl2RenderingContext::CLAMP_TO_EDGE as i32,
+    );
+    gl_context.tex_parameteri(
+      WebGl2RenderingContext::TEXTURE_2D,
+      WebGl2RenderingContext::TEXTURE_MIN_FILTER,
+      WebGl2RenderingContext::LINEAR as i32,
+    );
+  }
+
+  Ok(texture)
+}
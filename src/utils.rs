@@ -0,0 +1,17 @@
+use wasm_bindgen::{prelude::*, JsCast};
+
+/// Flatten a `nalgebra_glm::Mat4` into the column-major `[f32; 16]` layout
+/// `uniform_matrix4fv_with_f32_array` expects.
+pub fn mat4_to_f32_16(mat: nalgebra_glm::Mat4) -> [f32; 16] {
+  let mut out = [0.0f32; 16];
+  out.copy_from_slice(mat.as_slice());
+  out
+}
+
+/// Schedule `f` to run on the next animation frame via `window.requestAnimationFrame`.
+pub fn request_animation_frame(f: &Closure<dyn FnMut(f32)>) {
+  web_sys::window()
+    .expect("no global `window` exists")
+    .request_animation_frame(f.as_ref().unchecked_ref())
+    .expect("should register `requestAnimationFrame` OK");
+}